@@ -0,0 +1,52 @@
+//! `${VAR}` / `${VAR:-default}` substitution using the process environment.
+
+use regex::Regex;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExpandError {
+    #[error("Environment variable {0:?} is not set and has no default")]
+    Unset(String),
+    #[error("Pattern is invalid after expansion: {0}")]
+    InvalidRegex(#[from] regex::Error),
+}
+
+/// Replace every `${VAR}` or `${VAR:-default}` reference in `s` with the
+/// value of `VAR` from the process environment, falling back to
+/// `default` (or erroring) when `VAR` isn't set.
+pub fn expand(s: &str) -> Result<String, ExpandError> {
+    let re = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)(:-([^}]*))?\}").expect("valid regex");
+    let mut unset = None;
+    let expanded = re.replace_all(s, |caps: &regex::Captures| {
+        let name = &caps[1];
+        match std::env::var(name) {
+            Ok(value) => value,
+            Err(_) => match caps.get(3) {
+                Some(default) => default.as_str().to_owned(),
+                None => {
+                    unset.get_or_insert_with(|| ExpandError::Unset(name.to_owned()));
+                    String::new()
+                }
+            },
+        }
+    });
+    match unset {
+        Some(err) => Err(err),
+        None => Ok(expanded.into_owned()),
+    }
+}
+
+/// Recursively expand every string leaf of a [`serde_json::Value`].
+pub fn expand_value(value: &serde_json::Value) -> Result<serde_json::Value, ExpandError> {
+    Ok(match value {
+        serde_json::Value::String(s) => serde_json::Value::String(expand(s)?),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(expand_value).collect::<Result<_, _>>()?)
+        }
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(key, value)| expand_value(value).map(|value| (key.clone(), value)))
+                .collect::<Result<_, _>>()?,
+        ),
+        other => other.clone(),
+    })
+}
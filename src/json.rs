@@ -1,5 +1,6 @@
 use derive_more::IsVariant;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 #[derive(Debug, thiserror::Error)]
@@ -21,9 +22,14 @@ pub enum ValueProblem {
     },
     #[error("Not array or array member not matched")]
     NoArrayContains,
+    #[error("Object does not contain key {key:?}")]
+    NoObjectContains { key: String },
+    #[error("Object contains disallowed key {key:?}")]
+    DisallowedKeyPresent { key: String },
 }
 
-#[derive(Debug, IsVariant)]
+#[derive(Debug, Serialize, Deserialize, IsVariant)]
+#[serde(rename_all = "kebab-case")]
 pub enum ValueValidator {
     AnyValue,
     Type(HashSet<JsonType>),
@@ -31,7 +37,7 @@ pub enum ValueValidator {
     ExactNumber(serde_json::Number),
     NumericRange(serde_json::Number, serde_json::Number),
     ExactString(String),
-    RegexString(Regex),
+    RegexString(#[serde(with = "serde_regex")] Regex),
     ExactArray(Vec<serde_json::Value>),
     ArrayContains(Box<Self>),
     ObjectContains(String, Box<Self>),
@@ -69,7 +75,24 @@ impl ValueValidator {
                     actual: value.clone(),
                 }),
             },
-            NumericRange(_, _) => todo!(),
+            NumericRange(min, max) => {
+                let in_range = value
+                    .as_f64()
+                    .zip(min.as_f64())
+                    .zip(max.as_f64())
+                    .map(|((actual, min), max)| (min..=max).contains(&actual))
+                    .unwrap_or(false);
+                match in_range {
+                    true => Ok(()),
+                    false => Err(WrongValue {
+                        expected: serde_json::Value::Array(vec![
+                            serde_json::Value::Number(min.clone()),
+                            serde_json::Value::Number(max.clone()),
+                        ]),
+                        actual: value.clone(),
+                    }),
+                }
+            }
             ExactString(expected) => match value {
                 serde_json::Value::String(actual) if actual == expected => Ok(()),
                 _ => Err(WrongValue {
@@ -100,14 +123,101 @@ impl ValueValidator {
 
                 _ => Err(NoArrayContains),
             },
-            ObjectContains(expected_key, expected_value) => todo!(),
-            ObjectNotContains(_) => todo!(),
-            ExactObject(_) => todo!(),
+            ObjectContains(expected_key, expected_value) => match value {
+                serde_json::Value::Object(actual) => match actual.get(expected_key) {
+                    Some(actual_value) => expected_value.allows(actual_value),
+                    None => Err(NoObjectContains {
+                        key: expected_key.clone(),
+                    }),
+                },
+                _ => Err(NoObjectContains {
+                    key: expected_key.clone(),
+                }),
+            },
+            ObjectNotContains(disallowed_key) => match value {
+                serde_json::Value::Object(actual) if !actual.contains_key(disallowed_key) => Ok(()),
+                serde_json::Value::Object(_) => Err(DisallowedKeyPresent {
+                    key: disallowed_key.clone(),
+                }),
+                _ => Err(DisallowedType {
+                    allowed_types: HashSet::from([JsonType::Object]),
+                    actual_type: JsonType::of(value),
+                }),
+            },
+            ExactObject(expected) => match value {
+                serde_json::Value::Object(actual) if actual == expected => Ok(()),
+                _ => Err(WrongValue {
+                    expected: serde_json::Value::Object(expected.clone()),
+                    actual: value.clone(),
+                }),
+            },
+        }
+    }
+}
+
+/// Autogenerate a JSON Schema describing the shape of `value`: every
+/// object key present becomes a `required` property of the matching
+/// type, recursively. Scalars are described by type *and* their literal
+/// value (via `const`), so a present-but-wrong value is rejected, not
+/// just a present-but-wrong-typed one. Arrays are described by type
+/// alone.
+///
+/// When `exact` is set, every object level also gets
+/// `"additionalProperties": false`, so the schema rejects keys that
+/// `value` didn't mention.
+pub fn describe_value_with(value: &serde_json::Value, exact: bool) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let properties: serde_json::Map<_, _> = map
+                .iter()
+                .map(|(key, value)| (key.clone(), describe_value_with(value, exact)))
+                .collect();
+            let required: Vec<_> = map
+                .keys()
+                .map(|key| serde_json::Value::String(key.clone()))
+                .collect();
+            let mut schema = serde_json::json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            });
+            if exact {
+                schema["additionalProperties"] = serde_json::Value::Bool(false);
+            }
+            schema
+        }
+        serde_json::Value::Array(_) => serde_json::json!({"type": "array"}),
+        serde_json::Value::String(_) => serde_json::json!({"type": "string", "const": value}),
+        serde_json::Value::Number(_) => serde_json::json!({"type": "number", "const": value}),
+        serde_json::Value::Bool(_) => serde_json::json!({"type": "boolean", "const": value}),
+        serde_json::Value::Null => serde_json::json!({"type": "null"}),
+    }
+}
+
+/// Recursively merge `desired` into `base`.
+///
+/// For each key in `desired`, if both sides hold an object the merge
+/// recurses; otherwise `base`'s value is overwritten wholesale (this is
+/// also how arrays and scalars are handled). Keys present in `base` but
+/// absent from `desired` are left untouched.
+pub fn deep_merge(base: &mut serde_json::Value, desired: &serde_json::Value) {
+    match (base, desired) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(desired)) => {
+            for (key, desired_value) in desired {
+                match base.get_mut(key) {
+                    Some(base_value) => deep_merge(base_value, desired_value),
+                    None => {
+                        base.insert(key.clone(), desired_value.clone());
+                    }
+                }
+            }
         }
+        (base, desired) => *base = desired.clone(),
     }
 }
 
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum JsonType {
     Null,
     Bool,
@@ -60,9 +60,10 @@ use std::{
     fs, io,
     path::{Path, PathBuf},
 };
+mod env;
 mod json;
 
-use crate::json::describe_value;
+use crate::json::{deep_merge, describe_value_with, ValueValidator};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -82,13 +83,24 @@ pub enum FileCheck {
         exists: bool,
     },
     LooksLike {
-        format: FileFormat,
+        /// Inferred from the file extension if omitted.
+        #[serde(default)]
+        format: Option<FileFormat>,
         schema: serde_json::Value,
+        /// Fail if the file contains any keys not mentioned in `schema`.
+        #[serde(default)]
+        exact: bool,
     },
     #[serde(rename_all = "kebab-case")]
     MatchesRegex {
-        #[serde(with = "serde_regex")]
-        matches_regex: Regex,
+        /// The raw pattern text, compiled (after `${VAR}` interpolation)
+        /// when the check runs, rather than at config-parse time.
+        matches_regex: String,
+    },
+    ValueAt {
+        format: FileFormat,
+        pointer: String,
+        value: ValueValidator,
     },
 }
 
@@ -98,6 +110,193 @@ pub enum FileFormat {
     Json,
     Toml,
     Yaml,
+    Ini,
+    Env,
+}
+
+impl FileFormat {
+    /// Infer a format from a file's extension, for use when `format` is
+    /// omitted from a [`FileCheck::LooksLike`].
+    fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Some(Self::Toml),
+            Some("yaml" | "yml") => Some(Self::Yaml),
+            Some("json") => Some(Self::Json),
+            Some("ini") => Some(Self::Ini),
+            Some("env") => Some(Self::Env),
+            _ => None,
+        }
+    }
+}
+
+/// A deserialization failure, together with the dotted/indexed path
+/// (e.g. `package.dependencies.serde.version`) to the node that caused
+/// it, as reported by `serde_path_to_error`. Ini and env files are parsed
+/// without serde, so their errors carry an empty path.
+#[derive(Debug)]
+pub struct DeserializeError {
+    pub path: String,
+    pub source: anyhow::Error,
+}
+
+impl std::fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at {}: {}", self.path, self.source)
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+fn parse_value(format: FileFormat, s: &str) -> Result<serde_json::Value, DeserializeError> {
+    fn pathed<T, E: std::error::Error + Send + Sync + 'static>(
+        result: Result<T, serde_path_to_error::Error<E>>,
+    ) -> Result<T, DeserializeError> {
+        result.map_err(|err| DeserializeError {
+            path: err.path().to_string(),
+            source: anyhow::Error::new(err.into_inner()),
+        })
+    }
+    match format {
+        FileFormat::Json => pathed(serde_path_to_error::deserialize(
+            &mut serde_json::Deserializer::from_str(s),
+        )),
+        FileFormat::Toml => pathed(serde_path_to_error::deserialize(
+            toml::de::Deserializer::new(s),
+        )),
+        FileFormat::Yaml => pathed(serde_path_to_error::deserialize(
+            serde_yaml::Deserializer::from_str(s),
+        )),
+        FileFormat::Ini => parse_ini(s).map_err(|source| DeserializeError {
+            path: String::new(),
+            source,
+        }),
+        FileFormat::Env => parse_env(s).map_err(|source| DeserializeError {
+            path: String::new(),
+            source,
+        }),
+    }
+}
+
+fn render_value(format: FileFormat, value: &serde_json::Value) -> anyhow::Result<String> {
+    match format {
+        FileFormat::Json => Ok(serde_json::to_string_pretty(value)?),
+        FileFormat::Toml => Ok(toml::to_string_pretty(value)?),
+        FileFormat::Yaml => Ok(serde_yaml::to_string(value)?),
+        FileFormat::Ini => render_ini(value),
+        FileFormat::Env => render_env(value),
+    }
+}
+
+/// Parse dotenv-style `KEY=VALUE` lines into a flat JSON object.
+/// Blank lines and lines starting with `#` are ignored; values may be
+/// wrapped in matching single or double quotes.
+fn parse_env(s: &str) -> anyhow::Result<serde_json::Value> {
+    let mut map = serde_json::Map::new();
+    for line in s.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("Expected KEY=VALUE, got {line:?}"))?;
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .or_else(|| {
+                value
+                    .strip_prefix('\'')
+                    .and_then(|value| value.strip_suffix('\''))
+            })
+            .unwrap_or(value);
+        map.insert(
+            key.trim().to_owned(),
+            serde_json::Value::String(value.to_owned()),
+        );
+    }
+    Ok(serde_json::Value::Object(map))
+}
+
+/// Render a value as it should appear on the right-hand side of a
+/// `key=value` line: strings are written bare, everything else as JSON.
+fn scalar_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Render a flat JSON object as dotenv-style `KEY=VALUE` lines.
+fn render_env(value: &serde_json::Value) -> anyhow::Result<String> {
+    let map = value
+        .as_object()
+        .context("Env files can only hold a flat object of key-value pairs")?;
+    let mut out = String::new();
+    for (key, value) in map {
+        out.push_str(key);
+        out.push('=');
+        out.push_str(&scalar_to_string(value));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Parse INI text into a JSON object: keys before any `[section]`
+/// header land at the top level, keys within a section land in a
+/// nested object keyed by the section name.
+fn parse_ini(s: &str) -> anyhow::Result<serde_json::Value> {
+    let mut root = serde_json::Map::new();
+    let mut section: Option<String> = None;
+    for line in s.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = Some(name.to_owned());
+            root.entry(name.to_owned())
+                .or_insert_with(|| serde_json::Value::Object(Default::default()));
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("Expected key=value, got {line:?}"))?;
+        let entry = serde_json::Value::String(value.trim().to_owned());
+        match &section {
+            Some(name) => {
+                root.entry(name.clone())
+                    .or_insert_with(|| serde_json::Value::Object(Default::default()))
+                    .as_object_mut()
+                    .expect("sections are always objects")
+                    .insert(key.trim().to_owned(), entry);
+            }
+            None => {
+                root.insert(key.trim().to_owned(), entry);
+            }
+        }
+    }
+    Ok(serde_json::Value::Object(root))
+}
+
+/// Render a JSON object as INI text: top-level scalar keys are written
+/// un-sectioned, top-level object values become `[section]` blocks.
+fn render_ini(value: &serde_json::Value) -> anyhow::Result<String> {
+    let map = value
+        .as_object()
+        .context("Ini files can only hold an object at the top level")?;
+    let mut out = String::new();
+    let (sections, scalars): (Vec<_>, Vec<_>) = map.iter().partition(|(_, v)| v.is_object());
+    for (key, value) in scalars {
+        out.push_str(&format!("{key}={}\n", scalar_to_string(value)));
+    }
+    for (name, section) in sections {
+        out.push_str(&format!("[{name}]\n"));
+        for (key, value) in section.as_object().expect("partitioned as object") {
+            out.push_str(&format!("{key}={}\n", scalar_to_string(value)));
+        }
+    }
+    Ok(out)
 }
 
 pub fn check_items(
@@ -105,7 +304,8 @@ pub fn check_items(
     items: impl IntoIterator<Item = CheckItem>,
 ) -> anyhow::Result<Vec<Problem>> {
     use Problem::{
-        DisallowedFile, FileNotPresent, InvalidFormat, RegexNotMatched, SchemaNotMatched,
+        DisallowedFile, FileNotPresent, InvalidFormat, InvalidValue, PointerNotFound,
+        RegexNotMatched, SchemaNotMatched, UnknownFormat,
     };
     let mut problems = Vec::new();
     let root = root.as_ref().to_owned();
@@ -128,73 +328,371 @@ pub fn check_items(
                     FileCheck::LooksLike {
                         format,
                         schema: like,
+                        exact,
+                    } => {
+                        let format = match format.or_else(|| FileFormat::from_extension(&path)) {
+                            Some(format) => format,
+                            None => {
+                                problems.push(UnknownFormat(path));
+                                continue;
+                            }
+                        };
+                        match path.is_file() {
+                            true => {
+                                // Read to string since `toml` doesn't have a from_reader
+                                let s = fs::read_to_string(&path)
+                                    .context(format!("Couldn't read {}", path.display()))?;
+                                match parse_value(format, &s) {
+                                    Ok(v) => {
+                                        let schema = JSONSchema::compile(&describe_value_with(&like, exact))
+                                            .expect("Autogenerated schema generation failed, please file a bug report.");
+
+                                        if let Err(errors) = schema.validate(&v) {
+                                            problems.push(SchemaNotMatched {
+                                                path,
+                                                errors: errors
+                                                    .map(|validation_error| ValidationError {
+                                                        instance: Cow::Owned(
+                                                            validation_error.instance.into_owned(),
+                                                        ),
+                                                        ..validation_error
+                                                    })
+                                                    .collect(),
+                                            })
+                                        };
+                                    }
+                                    Err(err) => problems.push(InvalidFormat {
+                                        file: path,
+                                        format: format.into(),
+                                        path: err.path,
+                                        err: err.source,
+                                    }),
+                                }
+                            }
+                            false => problems.push(FileNotPresent(path)),
+                        }
+                    }
+                    FileCheck::MatchesRegex {
+                        matches_regex: pattern,
                     } => match path.is_file() {
                         true => {
-                            // Read to string since `toml` doesn't have a from_reader
+                            let regex = Regex::new(&pattern)
+                                .context(format!("Invalid regex {pattern:?}"))?;
                             let s = fs::read_to_string(&path)
                                 .context(format!("Couldn't read {}", path.display()))?;
-                            let deser_result = match format {
-                                FileFormat::Json => {
-                                    serde_json::from_str(&s).map_err(anyhow::Error::new)
-                                }
-                                FileFormat::Toml => toml::from_str(&s).map_err(anyhow::Error::new),
-                                FileFormat::Yaml => {
-                                    serde_yaml::from_str(&s).map_err(anyhow::Error::new)
-                                }
-                            };
-                            match deser_result {
-                                Ok(v) => {
-                                    let schema = JSONSchema::compile(&describe_value(&like))
-                                        .expect("Autogenerated schema generation failed, please file a bug report.");
-
-                                    if let Err(errors) = schema.validate(&v) {
-                                        problems.push(SchemaNotMatched {
-                                            path,
-                                            errors: errors
-                                                .map(|validation_error| ValidationError {
-                                                    instance: Cow::Owned(
-                                                        validation_error.instance.into_owned(),
-                                                    ),
-                                                    ..validation_error
-                                                })
-                                                .collect(),
-                                        })
-                                    };
-                                }
+                            if !regex.is_match(&s) {
+                                problems.push(RegexNotMatched { path, regex })
+                            }
+                        }
+                        false => problems.push(FileNotPresent(path)),
+                    },
+                    FileCheck::ValueAt {
+                        format,
+                        pointer,
+                        value: validator,
+                    } => match path.is_file() {
+                        true => {
+                            let s = fs::read_to_string(&path)
+                                .context(format!("Couldn't read {}", path.display()))?;
+                            match parse_value(format, &s) {
+                                Ok(v) => match v.pointer(&pointer) {
+                                    Some(at_pointer) => {
+                                        if let Err(problem) = validator.allows(at_pointer) {
+                                            problems.push(InvalidValue {
+                                                path,
+                                                pointer,
+                                                problem,
+                                            })
+                                        }
+                                    }
+                                    None => problems.push(PointerNotFound { path, pointer }),
+                                },
                                 Err(err) => problems.push(InvalidFormat {
-                                    path,
+                                    file: path,
                                     format: format.into(),
-                                    err,
+                                    path: err.path,
+                                    err: err.source,
                                 }),
                             }
                         }
                         false => problems.push(FileNotPresent(path)),
                     },
+                }
+            }
+        }
+    }
+    Ok(problems)
+}
+
+/// A change that [`fix_items`] would make, reported only in `dry_run`
+/// mode; a run that writes files returns no `Fix`es.
+#[derive(Debug)]
+pub enum Fix {
+    Created(PathBuf),
+    Removed(PathBuf),
+    Rewritten { path: PathBuf, diff: String },
+}
+
+impl std::fmt::Display for Fix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fix::Created(path) => write!(f, "Would create {}", path.display()),
+            Fix::Removed(path) => write!(f, "Would remove {}", path.display()),
+            Fix::Rewritten { path, diff } => {
+                writeln!(f, "--- {}", path.display())?;
+                writeln!(f, "+++ {}", path.display())?;
+                write!(f, "{diff}")
+            }
+        }
+    }
+}
+
+/// Like [`check_items`], but rather than only reporting [`Problem`]s,
+/// mutates files on disk to bring them into compliance.
+///
+/// `FileCheck::Exists` creates or removes the (empty) file as needed,
+/// creating parent directories when creating. `FileCheck::LooksLike`
+/// deep-merges `schema` into the existing document (or an empty document
+/// of the same format if the file doesn't yet exist) and re-serializes
+/// it in its original format. Since a merge can only add or overwrite
+/// keys, `exact: true` can leave the file still in violation (a stray
+/// key can't be merged away); this is re-checked after merging and
+/// reported as a remaining `Problem`, the same as an unfixable
+/// `FileCheck::MatchesRegex` or `FileCheck::ValueAt` mismatch.
+///
+/// With `dry_run`, no files are written; instead the [`Fix`]es that
+/// would have been made are returned alongside the remaining `Problem`s,
+/// for the caller to display.
+pub fn fix_items(
+    root: impl AsRef<Path>,
+    items: impl IntoIterator<Item = CheckItem>,
+    dry_run: bool,
+) -> anyhow::Result<(Vec<Fix>, Vec<Problem>)> {
+    use similar::{ChangeTag, TextDiff};
+    use Problem::RegexNotMatched;
+
+    let mut fixes = Vec::new();
+    let mut problems = Vec::new();
+    let root = root.as_ref().to_owned();
+    for item in items {
+        match item {
+            CheckItem::File { file, check } => {
+                let path = root.join(file);
+                match check {
+                    FileCheck::Exists {
+                        exists: should_exist,
+                    } => match (path.metadata(), should_exist) {
+                        (Ok(meta), false) if meta.is_file() => match dry_run {
+                            true => fixes.push(Fix::Removed(path)),
+                            false => fs::remove_file(&path)
+                                .context(format!("Couldn't remove {}", path.display()))?,
+                        },
+                        (Err(err), true) if err.kind() == io::ErrorKind::NotFound => {
+                            match dry_run {
+                                true => fixes.push(Fix::Created(path)),
+                                false => {
+                                    if let Some(parent) = path.parent() {
+                                        fs::create_dir_all(parent).context(format!(
+                                            "Couldn't create {}",
+                                            parent.display()
+                                        ))?;
+                                    }
+                                    fs::File::create(&path)
+                                        .context(format!("Couldn't create {}", path.display()))?;
+                                }
+                            }
+                        }
+                        _ => (),
+                    },
+                    FileCheck::LooksLike {
+                        format,
+                        schema: desired,
+                        exact,
+                    } => {
+                        let format = match format.or_else(|| FileFormat::from_extension(&path)) {
+                            Some(format) => format,
+                            None => {
+                                problems.push(Problem::UnknownFormat(path));
+                                continue;
+                            }
+                        };
+                        let original = match path.is_file() {
+                            true => fs::read_to_string(&path)
+                                .context(format!("Couldn't read {}", path.display()))?,
+                            false => String::new(),
+                        };
+                        let mut value = match original.is_empty() {
+                            false => parse_value(format, &original)
+                                .context(format!("Couldn't parse {}", path.display()))?,
+                            true => serde_json::json!({}),
+                        };
+                        deep_merge(&mut value, &desired);
+                        let rendered = render_value(format, &value)?;
+                        if rendered != original {
+                            match dry_run {
+                                true => {
+                                    let diff = TextDiff::from_lines(&original, &rendered)
+                                        .iter_all_changes()
+                                        .map(|change| {
+                                            let sign = match change.tag() {
+                                                ChangeTag::Delete => "-",
+                                                ChangeTag::Insert => "+",
+                                                ChangeTag::Equal => " ",
+                                            };
+                                            format!("{sign}{change}")
+                                        })
+                                        .collect();
+                                    fixes.push(Fix::Rewritten {
+                                        path: path.clone(),
+                                        diff,
+                                    });
+                                }
+                                false => {
+                                    if let Some(parent) = path.parent() {
+                                        fs::create_dir_all(parent).context(format!(
+                                            "Couldn't create {}",
+                                            parent.display()
+                                        ))?;
+                                    }
+                                    fs::write(&path, rendered)
+                                        .context(format!("Couldn't write {}", path.display()))?;
+                                }
+                            }
+                        }
+                        // `deep_merge` only ever adds or overwrites keys, so
+                        // under `exact: true` a stray key already present in
+                        // the file survives the merge untouched. Re-validate
+                        // the merged value and surface anything still wrong,
+                        // the same way an unfixable `MatchesRegex` mismatch is
+                        // reported below.
+                        if exact {
+                            let schema = JSONSchema::compile(&describe_value_with(&desired, exact))
+                                .expect("Autogenerated schema generation failed, please file a bug report.");
+                            if let Err(errors) = schema.validate(&value) {
+                                problems.push(Problem::SchemaNotMatched {
+                                    path,
+                                    errors: errors
+                                        .map(|validation_error| ValidationError {
+                                            instance: Cow::Owned(
+                                                validation_error.instance.into_owned(),
+                                            ),
+                                            ..validation_error
+                                        })
+                                        .collect(),
+                                })
+                            }
+                        }
+                    }
                     FileCheck::MatchesRegex {
-                        matches_regex: regex,
+                        matches_regex: pattern,
                     } => match path.is_file() {
                         true => {
+                            let regex = Regex::new(&pattern)
+                                .context(format!("Invalid regex {pattern:?}"))?;
                             let s = fs::read_to_string(&path)
                                 .context(format!("Couldn't read {}", path.display()))?;
                             if !regex.is_match(&s) {
                                 problems.push(RegexNotMatched { path, regex })
                             }
                         }
-                        false => problems.push(FileNotPresent(path)),
+                        false => problems.push(Problem::FileNotPresent(path)),
+                    },
+                    FileCheck::ValueAt {
+                        format,
+                        pointer,
+                        value: validator,
+                    } => match path.is_file() {
+                        true => {
+                            let s = fs::read_to_string(&path)
+                                .context(format!("Couldn't read {}", path.display()))?;
+                            match parse_value(format, &s) {
+                                Ok(v) => match v.pointer(&pointer) {
+                                    Some(at_pointer) => {
+                                        if let Err(problem) = validator.allows(at_pointer) {
+                                            problems.push(Problem::InvalidValue {
+                                                path,
+                                                pointer,
+                                                problem,
+                                            })
+                                        }
+                                    }
+                                    None => {
+                                        problems.push(Problem::PointerNotFound { path, pointer })
+                                    }
+                                },
+                                Err(err) => problems.push(Problem::InvalidFormat {
+                                    file: path,
+                                    format: format.into(),
+                                    path: err.path,
+                                    err: err.source,
+                                }),
+                            }
+                        }
+                        false => problems.push(Problem::FileNotPresent(path)),
                     },
                 }
             }
         }
     }
-    Ok(problems)
+    Ok((fixes, problems))
+}
+
+/// Expand `${VAR}` / `${VAR:-default}` references from the process
+/// environment in `s`. Exposed so callers can apply the same
+/// substitution to config fields that live outside [`CheckItem`], such
+/// as `include` URLs.
+pub fn interpolate_str(s: &str) -> anyhow::Result<String> {
+    Ok(env::expand(s)?)
+}
+
+/// Expand environment variable references in every [`CheckItem`]:
+/// `file` paths, `LooksLike.schema` string leaves, and `MatchesRegex`
+/// patterns.
+pub fn interpolate_items(items: Vec<CheckItem>) -> anyhow::Result<Vec<CheckItem>> {
+    items
+        .into_iter()
+        .map(|item| match item {
+            CheckItem::File { file, check } => {
+                let file = PathBuf::from(env::expand(&file.to_string_lossy())?);
+                let check = match check {
+                    FileCheck::Exists { exists } => FileCheck::Exists { exists },
+                    FileCheck::LooksLike {
+                        format,
+                        schema,
+                        exact,
+                    } => FileCheck::LooksLike {
+                        format,
+                        schema: env::expand_value(&schema)?,
+                        exact,
+                    },
+                    FileCheck::MatchesRegex { matches_regex } => FileCheck::MatchesRegex {
+                        matches_regex: env::expand(&matches_regex)?,
+                    },
+                    FileCheck::ValueAt {
+                        format,
+                        pointer,
+                        value,
+                    } => FileCheck::ValueAt {
+                        format,
+                        pointer,
+                        value,
+                    },
+                };
+                Ok(CheckItem::File { file, check })
+            }
+        })
+        .collect()
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Problem {
-    #[error("File {} couldn't be read in as {format}: {err:?}", .path.display())]
+    #[error("File {} couldn't be read in as {format} at {path}: {err:?}", .file.display())]
     InvalidFormat {
-        path: PathBuf,
+        file: PathBuf,
         format: &'static str,
+        /// Dotted/indexed path to the node that failed to deserialize
+        /// (empty for ini and env files).
+        path: String,
         err: anyhow::Error,
     },
     #[error("Schema not matched in {}:\n\t{}", .path.display(), .errors.iter().join("\n\t"))]
@@ -208,6 +706,16 @@ pub enum Problem {
     FileNotPresent(PathBuf),
     #[error("File {} is not allowed to exist", .0.display())]
     DisallowedFile(PathBuf),
+    #[error("Pointer {pointer} not found in {}", .path.display())]
+    PointerNotFound { path: PathBuf, pointer: String },
+    #[error("Value at {pointer} in {} is invalid: {problem}", .path.display())]
+    InvalidValue {
+        path: PathBuf,
+        pointer: String,
+        problem: json::ValueProblem,
+    },
+    #[error("Couldn't infer a format for {} from its extension; pass `format` explicitly", .0.display())]
+    UnknownFormat(PathBuf),
 }
 
 impl CheckItem {
@@ -221,12 +729,11 @@ impl CheckItem {
 
 #[cfg(test)]
 mod tests {
-    use regex::Regex;
     use serde_json::json;
     use std::fs::{self, File};
     use tempfile::tempdir;
 
-    use crate::{check_items, CheckItem, FileCheck, FileFormat, Problem};
+    use crate::{check_items, fix_items, CheckItem, FileCheck, FileFormat, Fix, Problem};
 
     #[test]
     fn empty_directory() -> anyhow::Result<()> {
@@ -268,8 +775,9 @@ mod tests {
             [CheckItem::file(
                 "foo.toml",
                 FileCheck::LooksLike {
-                    format: FileFormat::Toml,
+                    format: Some(FileFormat::Toml),
                     schema: json!({"hello": {"world": true}}),
+                    exact: false,
                 },
             )],
         )?;
@@ -281,8 +789,9 @@ mod tests {
             [CheckItem::file(
                 "foo.toml",
                 FileCheck::LooksLike {
-                    format: FileFormat::Toml,
+                    format: Some(FileFormat::Toml),
                     schema: json!({"hello": {"world": false}}),
+                    exact: false,
                 },
             )],
         )?;
@@ -295,6 +804,176 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn schema_validation_exact() -> anyhow::Result<()> {
+        let d = tempdir()?;
+        fs::write(d.path().join("foo.toml"), "[hello]\nworld = true")?;
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.toml",
+                FileCheck::LooksLike {
+                    format: Some(FileFormat::Toml),
+                    schema: json!({"hello": {"world": true}}),
+                    exact: true,
+                },
+            )],
+        )?;
+        println!("{problems:?}");
+        assert!(matches!(problems.as_slice(), []));
+
+        fs::write(
+            d.path().join("foo.toml"),
+            "[hello]\nworld = true\nextra = true",
+        )?;
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.toml",
+                FileCheck::LooksLike {
+                    format: Some(FileFormat::Toml),
+                    schema: json!({"hello": {"world": true}}),
+                    exact: true,
+                },
+            )],
+        )?;
+        println!("{problems:?}");
+        assert!(matches!(
+            problems.as_slice(),
+            [Problem::SchemaNotMatched { .. }]
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_inference() -> anyhow::Result<()> {
+        let d = tempdir()?;
+        fs::write(d.path().join("foo.toml"), "[hello]\nworld = true")?;
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.toml",
+                FileCheck::LooksLike {
+                    format: None,
+                    schema: json!({"hello": {"world": true}}),
+                    exact: false,
+                },
+            )],
+        )?;
+        println!("{problems:?}");
+        assert!(matches!(problems.as_slice(), []));
+
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.unknown-extension",
+                FileCheck::LooksLike {
+                    format: None,
+                    schema: json!({}),
+                    exact: false,
+                },
+            )],
+        )?;
+        println!("{problems:?}");
+        assert!(matches!(problems.as_slice(), [Problem::UnknownFormat(_)]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn ini_and_env_formats() -> anyhow::Result<()> {
+        let d = tempdir()?;
+        fs::write(d.path().join("foo.ini"), "[hello]\nworld = true")?;
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.ini",
+                FileCheck::LooksLike {
+                    format: None,
+                    schema: json!({"hello": {"world": "true"}}),
+                    exact: false,
+                },
+            )],
+        )?;
+        println!("{problems:?}");
+        assert!(matches!(problems.as_slice(), []));
+
+        fs::write(d.path().join("foo.env"), "HELLO=world\n")?;
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.env",
+                FileCheck::LooksLike {
+                    format: None,
+                    schema: json!({"HELLO": "world"}),
+                    exact: false,
+                },
+            )],
+        )?;
+        println!("{problems:?}");
+        assert!(matches!(problems.as_slice(), []));
+
+        Ok(())
+    }
+
+    #[test]
+    fn interpolation() -> anyhow::Result<()> {
+        std::env::set_var("YOU_MUST_CONFORM_TEST_VAR", "interpolated");
+
+        let items = crate::interpolate_items(vec![CheckItem::file(
+            "${YOU_MUST_CONFORM_TEST_VAR}.toml",
+            FileCheck::LooksLike {
+                format: Some(FileFormat::Toml),
+                schema: json!({"hello": "${YOU_MUST_CONFORM_TEST_VAR}"}),
+                exact: false,
+            },
+        )])?;
+
+        match items.as_slice() {
+            [CheckItem::File { file, check }] => {
+                assert_eq!(file, std::path::Path::new("interpolated.toml"));
+                match check {
+                    FileCheck::LooksLike { schema, .. } => {
+                        assert_eq!(schema, &json!({"hello": "interpolated"}))
+                    }
+                    other => panic!("unexpected check: {other:?}"),
+                }
+            }
+            other => panic!("unexpected items: {other:?}"),
+        }
+
+        let err = crate::interpolate_items(vec![CheckItem::file(
+            "${YOU_MUST_CONFORM_TEST_VAR_UNSET}",
+            FileCheck::Exists { exists: true },
+        )]);
+        assert!(err.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn regex_pattern_interpolation() -> anyhow::Result<()> {
+        std::env::set_var("YOU_MUST_CONFORM_REGEX_VAR", "bar");
+
+        // `${VAR}` is invalid regex syntax, so the raw pattern must round-trip
+        // through deserialization as plain text, not a compiled `Regex`.
+        let item: CheckItem = serde_yaml::from_str(
+            "file: foo.txt\nmatches-regex: \"^${YOU_MUST_CONFORM_REGEX_VAR}_baz\"",
+        )?;
+
+        let items = crate::interpolate_items(vec![item])?;
+        match items.as_slice() {
+            [CheckItem::File {
+                check: FileCheck::MatchesRegex { matches_regex },
+                ..
+            }] => assert_eq!(matches_regex, "^bar_baz"),
+            other => panic!("unexpected items: {other:?}"),
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn regex_matching() -> anyhow::Result<()> {
         let d = tempdir()?;
@@ -304,7 +983,7 @@ mod tests {
             [CheckItem::file(
                 "bar",
                 FileCheck::MatchesRegex {
-                    matches_regex: Regex::new("barth")?,
+                    matches_regex: "barth".to_owned(),
                 },
             )],
         )?;
@@ -316,7 +995,7 @@ mod tests {
             [CheckItem::file(
                 "bar",
                 FileCheck::MatchesRegex {
-                    matches_regex: Regex::new("foo")?,
+                    matches_regex: "foo".to_owned(),
                 },
             )],
         )?;
@@ -328,4 +1007,251 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn value_at_validates_pointer() -> anyhow::Result<()> {
+        use crate::json::ValueValidator;
+
+        let d = tempdir()?;
+        fs::write(d.path().join("foo.json"), r#"{"version": 2}"#)?;
+
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.json",
+                FileCheck::ValueAt {
+                    format: FileFormat::Json,
+                    pointer: "/version".to_owned(),
+                    value: ValueValidator::NumericRange(1.into(), 3.into()),
+                },
+            )],
+        )?;
+        println!("{problems:?}");
+        assert!(matches!(problems.as_slice(), []));
+
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.json",
+                FileCheck::ValueAt {
+                    format: FileFormat::Json,
+                    pointer: "/version".to_owned(),
+                    value: ValueValidator::NumericRange(10.into(), 20.into()),
+                },
+            )],
+        )?;
+        println!("{problems:?}");
+        assert!(matches!(
+            problems.as_slice(),
+            [Problem::InvalidValue { .. }]
+        ));
+
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.json",
+                FileCheck::ValueAt {
+                    format: FileFormat::Json,
+                    pointer: "/missing".to_owned(),
+                    value: ValueValidator::AnyValue,
+                },
+            )],
+        )?;
+        println!("{problems:?}");
+        assert!(matches!(
+            problems.as_slice(),
+            [Problem::PointerNotFound { .. }]
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn value_validator_object_arms() {
+        use crate::json::ValueValidator;
+
+        let object = json!({"present": 1});
+
+        assert!(ValueValidator::ObjectContains(
+            "present".to_owned(),
+            Box::new(ValueValidator::ExactNumber(1.into()))
+        )
+        .allows(&object)
+        .is_ok());
+        assert!(ValueValidator::ObjectContains(
+            "absent".to_owned(),
+            Box::new(ValueValidator::AnyValue)
+        )
+        .allows(&object)
+        .is_err());
+
+        assert!(ValueValidator::ObjectNotContains("absent".to_owned())
+            .allows(&object)
+            .is_ok());
+        assert!(ValueValidator::ObjectNotContains("present".to_owned())
+            .allows(&object)
+            .is_err());
+
+        assert!(
+            ValueValidator::ExactObject(object.as_object().unwrap().clone())
+                .allows(&object)
+                .is_ok()
+        );
+        assert!(ValueValidator::ExactObject(Default::default())
+            .allows(&object)
+            .is_err());
+    }
+
+    #[test]
+    fn fix_creates_and_removes_files() -> anyhow::Result<()> {
+        let d = tempdir()?;
+
+        let (fixes, problems) = fix_items(
+            &d,
+            [CheckItem::file("foo", FileCheck::Exists { exists: true })],
+            false,
+        )?;
+        assert!(matches!(fixes.as_slice(), []));
+        assert!(matches!(problems.as_slice(), []));
+        assert!(d.path().join("foo").is_file());
+
+        let (fixes, problems) = fix_items(
+            &d,
+            [CheckItem::file("foo", FileCheck::Exists { exists: false })],
+            false,
+        )?;
+        assert!(matches!(fixes.as_slice(), []));
+        assert!(matches!(problems.as_slice(), []));
+        assert!(!d.path().join("foo").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fix_dry_run_reports_without_writing() -> anyhow::Result<()> {
+        let d = tempdir()?;
+
+        let (fixes, problems) = fix_items(
+            &d,
+            [CheckItem::file("foo", FileCheck::Exists { exists: true })],
+            true,
+        )?;
+        assert!(matches!(fixes.as_slice(), [Fix::Created(_)]));
+        assert!(matches!(problems.as_slice(), []));
+        assert!(!d.path().join("foo").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fix_merges_looks_like_schema() -> anyhow::Result<()> {
+        let d = tempdir()?;
+        fs::write(d.path().join("foo.toml"), "[hello]\nworld = true\n")?;
+
+        let (fixes, problems) = fix_items(
+            &d,
+            [CheckItem::file(
+                "foo.toml",
+                FileCheck::LooksLike {
+                    format: Some(FileFormat::Toml),
+                    schema: json!({"hello": {"new-key": "new-value"}}),
+                    exact: false,
+                },
+            )],
+            false,
+        )?;
+        assert!(matches!(fixes.as_slice(), []));
+        assert!(matches!(problems.as_slice(), []));
+
+        let written = fs::read_to_string(d.path().join("foo.toml"))?;
+        let value: toml::Value = toml::from_str(&written)?;
+        assert_eq!(value["hello"]["world"].as_bool(), Some(true));
+        assert_eq!(value["hello"]["new-key"].as_str(), Some("new-value"));
+
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.toml",
+                FileCheck::LooksLike {
+                    format: Some(FileFormat::Toml),
+                    schema: json!({"hello": {"new-key": "new-value"}}),
+                    exact: false,
+                },
+            )],
+        )?;
+        assert!(matches!(problems.as_slice(), []));
+
+        Ok(())
+    }
+
+    #[test]
+    fn fix_reports_unremoved_keys_under_exact() -> anyhow::Result<()> {
+        let d = tempdir()?;
+        fs::write(
+            d.path().join("foo.toml"),
+            "[hello]\nworld = true\nextra = 1\n",
+        )?;
+
+        // `deep_merge` can't remove `hello.extra`, so `exact: true` should
+        // still be reported as unfixed, not silently dropped.
+        let (fixes, problems) = fix_items(
+            &d,
+            [CheckItem::file(
+                "foo.toml",
+                FileCheck::LooksLike {
+                    format: Some(FileFormat::Toml),
+                    schema: json!({"hello": {"world": true}}),
+                    exact: true,
+                },
+            )],
+            false,
+        )?;
+        assert!(matches!(fixes.as_slice(), []));
+        assert!(matches!(
+            problems.as_slice(),
+            [Problem::SchemaNotMatched { .. }]
+        ));
+
+        let problems = check_items(
+            &d,
+            [CheckItem::file(
+                "foo.toml",
+                FileCheck::LooksLike {
+                    format: Some(FileFormat::Toml),
+                    schema: json!({"hello": {"world": true}}),
+                    exact: true,
+                },
+            )],
+        )?;
+        assert!(matches!(
+            problems.as_slice(),
+            [Problem::SchemaNotMatched { .. }]
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn fix_cannot_repair_regex_mismatch() -> anyhow::Result<()> {
+        let d = tempdir()?;
+        fs::write(d.path().join("bar"), "nope")?;
+
+        let (fixes, problems) = fix_items(
+            &d,
+            [CheckItem::file(
+                "bar",
+                FileCheck::MatchesRegex {
+                    matches_regex: "yep".to_owned(),
+                },
+            )],
+            false,
+        )?;
+        assert!(matches!(fixes.as_slice(), []));
+        assert!(matches!(
+            problems.as_slice(),
+            [Problem::RegexNotMatched { .. }]
+        ));
+
+        Ok(())
+    }
 }
@@ -32,24 +32,65 @@ struct Args {
     context: PathBuf,
     #[clap(short, long, help = "A url to fetch the config file from instead")]
     url: Option<Url>,
+    #[clap(
+        long,
+        help = "Mutate files to bring them into compliance, instead of only reporting problems"
+    )]
+    fix: bool,
+    #[clap(
+        long,
+        requires = "fix",
+        help = "With --fix, print the changes that would be made instead of writing them"
+    )]
+    dry_run: bool,
+}
+
+/// The `api-version` range this binary understands. Bump the major
+/// component whenever a config schema change isn't backward compatible.
+const SUPPORTED_API_VERSION: &str = "^1";
+
+/// Refuse to proceed with a config declaring an `api-version` outside
+/// [`SUPPORTED_API_VERSION`], rather than risk silently misinterpreting
+/// `FileCheck` variants a newer schema might have added.
+fn check_api_version(version: &semver::Version) -> anyhow::Result<()> {
+    let req = semver::VersionReq::parse(SUPPORTED_API_VERSION).expect("valid version req");
+    match req.matches(version) {
+        true => Ok(()),
+        false => bail!(
+            "Config declares api-version {version}, but this binary only understands {SUPPORTED_API_VERSION}"
+        ),
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Config {
     config: Vec<CheckItem>,
+    /// Raw (un-parsed) URLs: `Url::parse` percent-encodes `{`/`}`, which
+    /// would otherwise corrupt a `${VAR}` reference before interpolation
+    /// ever sees it, so parsing is deferred until after expansion.
     #[serde(default)]
-    include: Vec<Url>,
+    include: Vec<String>,
+    #[serde(rename = "api-version", default)]
+    api_version: Option<semver::Version>,
 }
 
 impl Config {
     #[async_recursion::async_recursion]
     async fn resolve(self) -> anyhow::Result<Vec<CheckItem>> {
         let Self {
-            mut config,
+            config,
             include,
+            api_version,
         } = self;
+        if let Some(version) = &api_version {
+            check_api_version(version)?;
+        }
+        let mut config = you_must_conform::interpolate_items(config)?;
 
         let resolve_includes = include.into_iter().map(|url| async move {
+            let expanded = you_must_conform::interpolate_str(&url)?;
+            let url = Url::parse(&expanded)
+                .context(format!("Couldn't parse interpolated url {expanded}"))?;
             let response = reqwest::get(url.clone())
                 .await
                 .and_then(Response::error_for_status)
@@ -58,8 +99,15 @@ impl Config {
                 .text()
                 .await
                 .context(format!("Couldn't decode response from {url}"))?;
-            let config: Config = serde_yaml::from_str(&text)
-                .context(format!("Couldn't serialize config from {url}"))?;
+            let config: Config =
+                serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(&text))
+                    .map_err(|err| {
+                        let path = err.path().to_string();
+                        anyhow::anyhow!(
+                            "Couldn't parse config from {url} at {path}: {}",
+                            err.into_inner()
+                        )
+                    })?;
             anyhow::Ok(config.resolve().await.context("Nested resolution failed")?)
         });
 
@@ -81,17 +129,35 @@ async fn main() -> anyhow::Result<()> {
     let config = match args.url {
         Some(url) => Config {
             config: vec![],
-            include: vec![url],
+            include: vec![url.to_string()],
+            api_version: None,
         },
         None => {
             let file = File::open(&args.file)
                 .context(format!("Couldn't open config file {}", args.file.display()))?;
-            serde_yaml::from_reader(file).context("Couldn't parse config")?
+            serde_path_to_error::deserialize(serde_yaml::Deserializer::from_reader(file)).map_err(
+                |err| {
+                    let path = err.path().to_string();
+                    anyhow::anyhow!("Couldn't parse config at {path}: {}", err.into_inner())
+                },
+            )?
         }
     };
     let items = config.resolve().await?;
-    let problems = you_must_conform::check_items(args.context, items)
-        .context("Unable to complete checking")?;
+    let problems = match args.fix {
+        true => {
+            let (fixes, problems) = you_must_conform::fix_items(args.context, items, args.dry_run)
+                .context("Unable to complete fix")?;
+            if args.dry_run {
+                for fix in fixes {
+                    println!("{fix}");
+                }
+            }
+            problems
+        }
+        false => you_must_conform::check_items(args.context, items)
+            .context("Unable to complete checking")?,
+    };
     match problems.len() {
         0 => Ok(()),
         n => {
@@ -102,3 +168,29 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{check_api_version, Config};
+
+    #[test]
+    fn config_parse_error_reports_field_path() {
+        let yaml = "config: []\ninclude: not-a-list\n";
+        let err =
+            serde_path_to_error::deserialize::<_, Config>(serde_yaml::Deserializer::from_str(yaml))
+                .expect_err("include should fail to deserialize as a list");
+        assert_eq!(err.path().to_string(), "include");
+    }
+
+    #[test]
+    fn api_version_accepts_matching_major() {
+        assert!(check_api_version(&"1.0.0".parse().unwrap()).is_ok());
+        assert!(check_api_version(&"1.4.2".parse().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn api_version_rejects_other_major() {
+        assert!(check_api_version(&"0.9.0".parse().unwrap()).is_err());
+        assert!(check_api_version(&"2.0.0".parse().unwrap()).is_err());
+    }
+}